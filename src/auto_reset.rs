@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+
+use crate::{Backoff, Strategy};
+
+/// A strategy wrapper which restarts the schedule after a stable period,
+/// like `CrashLoopBackOff` of Kubernetes.
+///
+/// The schedule is reset when the next duration is requested more than `stable` after
+/// the last duration has elapsed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// let strategy = retry_durations::builder().build().unwrap();
+/// let mut xs = retry_durations::AutoReset::new(strategy, Duration::from_secs(600));
+/// let x = xs.next();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AutoReset {
+    strategy: Strategy,
+    stable: Duration,
+    elapsed_at: Option<Instant>,
+}
+
+impl AutoReset {
+    /// Wrap `strategy` to reset it after `stable` period.
+    pub fn new(strategy: Strategy, stable: Duration) -> Self {
+        Self {
+            strategy,
+            stable,
+            elapsed_at: None,
+        }
+    }
+
+    /// Return the next duration as if requested at `now`.
+    pub fn next_at(&mut self, now: Instant) -> Option<Duration> {
+        self.advance(now, None)
+    }
+
+    fn advance(&mut self, now: Instant, hint: Option<Duration>) -> Option<Duration> {
+        if let Some(elapsed_at) = self.elapsed_at {
+            if self.stable < now.saturating_duration_since(elapsed_at) {
+                self.strategy.reset();
+            }
+        }
+        if let Some(hint) = hint {
+            self.strategy.retry_after(hint);
+        }
+        let duration = self.strategy.next()?;
+        self.elapsed_at = now.checked_add(duration);
+        Some(duration)
+    }
+
+    /// Restart the schedule from the initial duration.
+    pub fn reset(&mut self) {
+        self.strategy.reset();
+        self.elapsed_at = None;
+    }
+
+    /// Unwrap the inner strategy.
+    pub fn into_inner(self) -> Strategy {
+        self.strategy
+    }
+}
+
+impl Iterator for AutoReset {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_at(Instant::now())
+    }
+}
+
+/// `AutoReset` is sequential, so `attempt` is ignored.
+impl Backoff for AutoReset {
+    fn next_delay(&mut self, _attempt: u32) -> Option<Duration> {
+        self.advance(Instant::now(), None)
+    }
+
+    fn next_delay_with_hint(&mut self, _attempt: u32, hint: Duration) -> Option<Duration> {
+        self.advance(Instant::now(), Some(hint))
+    }
+
+    fn reset(&mut self) {
+        AutoReset::reset(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_resets_after_stable_period() {
+        let strategy = crate::builder()
+            .duration(Duration::from_secs(1))
+            .jitter(0.0)
+            .build()
+            .unwrap();
+        let mut xs = AutoReset::new(strategy, Duration::from_secs(60));
+        let t = Instant::now();
+
+        assert_eq!(xs.next_at(t), Some(Duration::from_secs(1)));
+        assert_eq!(
+            xs.next_at(t + Duration::from_secs(1)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            xs.next_at(t + Duration::from_secs(63)),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(
+            xs.next_at(t + Duration::from_secs(128)),
+            Some(Duration::from_secs(1))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::{borrow::Borrow, collections::HashMap, error::Error, fmt, hash::Hash, time::Duration};
+
+use crate::Strategy;
+
+/// A set of strategies selected by a class of errors, such as connection resets or throttling.
+///
+/// Each strategy advances independently, while max retry count and max total duration
+/// are shared among them.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use retry_durations::Classified;
+///
+/// let mut xs = Classified::new();
+/// xs.insert(
+///     "reset",
+///     retry_durations::builder()
+///         .duration(Duration::from_millis(100))
+///         .fixed()
+///         .build()
+///         .unwrap(),
+/// );
+/// xs.insert("throttle", retry_durations::builder().build().unwrap());
+/// xs.max_retries(10);
+///
+/// assert!(xs.next_for("throttle").unwrap().is_some());
+/// assert!(xs.next_for("reset").unwrap().is_some());
+/// assert!(xs.next_for("unknown").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Classified<K> {
+    strategies: HashMap<K, Strategy>,
+    max_retries: Option<u32>,
+    max_elapsed: Option<Duration>,
+    attempt: u32,
+    elapsed: Duration,
+    exhausted: bool,
+}
+
+/// An error of an unknown class, returned by [`Classified::next_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownClass;
+
+impl fmt::Display for UnknownClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown class")
+    }
+}
+
+impl Error for UnknownClass {}
+
+impl<K> Default for Classified<K> {
+    fn default() -> Self {
+        Self {
+            strategies: HashMap::new(),
+            max_retries: None,
+            max_elapsed: None,
+            attempt: 0,
+            elapsed: Duration::ZERO,
+            exhausted: false,
+        }
+    }
+}
+
+impl<K: Eq + Hash> Classified<K> {
+    /// Create a new empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a strategy of the class `key`, returning the previous one.
+    pub fn insert(&mut self, key: K, strategy: Strategy) -> Option<Strategy> {
+        self.strategies.insert(key, strategy)
+    }
+
+    /// Set max retry count shared among all classes.
+    pub fn max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Set max total of durations shared among all classes.
+    pub fn max_elapsed(&mut self, max_elapsed: Duration) -> &mut Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Return the next duration of the class `key`.
+    ///
+    /// Returns `Ok(None)` when the strategy of the class has ended, or shared limits are exceeded.
+    /// Once shared limits are exceeded, all classes return `Ok(None)` until `reset`.
+    /// A strategy is not advanced when shared limits reject its next duration.
+    /// Returns `Err(UnknownClass)` when the class is unknown, without changing anything.
+    pub fn next_for<Q>(&mut self, key: &Q) -> Result<Option<Duration>, UnknownClass>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let strategy = self.strategies.get_mut(key).ok_or(UnknownClass)?;
+        if self.exhausted || self.max_retries.is_some_and(|max| max <= self.attempt) {
+            self.exhausted = true;
+            return Ok(None);
+        }
+        let mut next_strategy = strategy.clone();
+        let Some(duration) = next_strategy.next() else {
+            *strategy = next_strategy;
+            return Ok(None);
+        };
+        let elapsed = self.elapsed.saturating_add(duration);
+        if self.max_elapsed.is_some_and(|max| max < elapsed) {
+            self.exhausted = true;
+            return Ok(None);
+        }
+        *strategy = next_strategy;
+        self.attempt += 1;
+        self.elapsed = elapsed;
+        Ok(Some(duration))
+    }
+
+    /// Restart all strategies and shared limits.
+    pub fn reset(&mut self) {
+        self.strategies.values_mut().for_each(Strategy::reset);
+        self.attempt = 0;
+        self.elapsed = Duration::ZERO;
+        self.exhausted = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classified() -> Classified<String> {
+        let mut xs = Classified::new();
+        xs.insert(
+            "reset".to_string(),
+            crate::builder()
+                .duration(Duration::from_millis(100))
+                .fixed()
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+        );
+        xs.insert(
+            "throttle".to_string(),
+            crate::builder()
+                .duration(Duration::from_secs(1))
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+        );
+        xs
+    }
+
+    #[test]
+    fn it_selects_strategies() {
+        let mut xs = classified();
+        xs.max_retries(4).max_elapsed(Duration::from_secs(10));
+
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(1))));
+        assert_eq!(xs.next_for("reset"), Ok(Some(Duration::from_millis(100))));
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(2))));
+        assert_eq!(xs.next_for("unknown"), Err(UnknownClass));
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(4))));
+        assert_eq!(xs.next_for("reset"), Ok(None));
+
+        xs.reset();
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(1))));
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(2))));
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(4))));
+        assert_eq!(xs.next_for("throttle"), Ok(None));
+    }
+
+    #[test]
+    fn it_keeps_shared_budget_exhausted() {
+        let mut xs = classified();
+        xs.max_elapsed(Duration::from_secs(5));
+
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(1))));
+        assert_eq!(xs.next_for("throttle"), Ok(Some(Duration::from_secs(2))));
+        assert_eq!(xs.next_for("throttle"), Ok(None));
+        assert_eq!(xs.next_for("reset"), Ok(None));
+        assert_eq!(xs.next_for("unknown"), Err(UnknownClass));
+
+        let throttle = xs.strategies["throttle"].clone();
+        assert_eq!(
+            throttle.take(1).collect::<Vec<_>>(),
+            [Duration::from_secs(4)]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{HintPolicy, Jitter, StrategyBuilder};
+
+/// A serializable configuration of a strategy, such as in service config files.
+///
+/// Durations are written in humantime format like `"250ms"` and `"2m"`.
+/// Omitted settings are defaults of the builder.
+///
+/// # Examples
+///
+/// ```rust
+/// let config: retry_durations::Config = serde_json::from_str(r#"{
+///     "duration": "250ms",
+///     "duration_max": "2m",
+///     "kind": { "linear": "500ms" },
+///     "jitter": "full",
+///     "max_retries": 10
+/// }"#).unwrap();
+/// let xs = config.builder().build().unwrap();
+/// assert_eq!(xs.remaining(), Some(10));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Initial duration.
+    #[serde(with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Duration>,
+    /// Max durations.
+    #[serde(with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub duration_max: Option<Duration>,
+    /// Min durations.
+    #[serde(with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub duration_min: Option<Duration>,
+    /// Interval strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ConfigKind>,
+    /// Growth factor of exponential interval strategy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiplier: Option<f64>,
+    /// Jitter mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jitter: Option<Jitter>,
+    /// Max total of durations.
+    #[serde(with = "humantime_serde", skip_serializing_if = "Option::is_none")]
+    pub max_elapsed: Option<Duration>,
+    /// Whether the last duration is truncated to fit in max total of durations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate_elapsed: Option<bool>,
+    /// Max retry count.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    /// How a server provided hint is applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint_policy: Option<HintPolicy>,
+}
+
+/// An interval strategy of [`Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigKind {
+    /// Fixed interval strategy.
+    Fixed,
+    /// Exponential interval strategy.
+    Exponential,
+    /// Linear interval strategy with the step.
+    Linear(#[serde(with = "humantime_serde")] Duration),
+    /// Fibonacci interval strategy.
+    Fibonacci,
+    /// Decorrelated jitter strategy.
+    Decorrelated,
+}
+
+impl Config {
+    /// Create a new Strategy builder of this configuration.
+    pub fn builder(&self) -> StrategyBuilder {
+        let mut builder = crate::builder();
+        if let Some(duration) = self.duration {
+            builder.duration(duration);
+        }
+        if let Some(duration_max) = self.duration_max {
+            builder.duration_max(duration_max);
+        }
+        if let Some(duration_min) = self.duration_min {
+            builder.duration_min(duration_min);
+        }
+        match self.kind {
+            Some(ConfigKind::Fixed) => builder.fixed(),
+            Some(ConfigKind::Exponential) | None => builder.exponential(),
+            Some(ConfigKind::Linear(step)) => builder.linear(step),
+            Some(ConfigKind::Fibonacci) => builder.fibonacci(),
+            Some(ConfigKind::Decorrelated) => builder.decorrelated(),
+        };
+        if let Some(multiplier) = self.multiplier {
+            builder.multiplier(multiplier);
+        }
+        if let Some(jitter) = self.jitter {
+            builder.jitter(jitter);
+        }
+        if let Some(max_elapsed) = self.max_elapsed {
+            builder.max_elapsed(max_elapsed);
+        }
+        if let Some(truncate_elapsed) = self.truncate_elapsed {
+            builder.truncate_elapsed(truncate_elapsed);
+        }
+        if let Some(max_retries) = self.max_retries {
+            builder.max_retries(max_retries);
+        }
+        if let Some(hint_policy) = self.hint_policy {
+            builder.hint_policy(hint_policy);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_deserializes_humantime() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "duration": "250ms",
+                "duration_max": "2m",
+                "kind": "fixed",
+                "jitter": { "ratio": 0.0 },
+                "max_elapsed": "1s",
+                "hint_policy": "max"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                duration: Some(Duration::from_millis(250)),
+                duration_max: Some(Duration::from_secs(120)),
+                kind: Some(ConfigKind::Fixed),
+                jitter: Some(Jitter::Ratio(0.0)),
+                max_elapsed: Some(Duration::from_secs(1)),
+                hint_policy: Some(HintPolicy::Max),
+                ..Config::default()
+            }
+        );
+        let xs: Vec<_> = config.builder().build().unwrap().collect();
+        assert_eq!(xs, [Duration::from_millis(250); 4]);
+    }
+
+    #[test]
+    fn it_round_trips() {
+        let config = Config {
+            duration: Some(Duration::from_secs(1)),
+            kind: Some(ConfigKind::Linear(Duration::from_millis(500))),
+            max_retries: Some(3),
+            ..Config::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"duration":"1s","kind":{"linear":"500ms"},"max_retries":3}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+        assert!(serde_json::from_str::<Config>(r#"{"unknown":1}"#).is_err());
+    }
+}
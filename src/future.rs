@@ -0,0 +1,250 @@
+use std::future::Future;
+
+use crate::{Backoff, Classify, Decision, RetryError, Sleeper};
+
+/// Run async `op` until it succeeds, awaiting `sleeper` of each duration of `strategy`
+/// between attempts.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::time::Duration;
+/// use retry_durations::ManualSleeper;
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_secs(1))
+///     .jitter(0.0)
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// let sleeper = ManualSleeper::new();
+/// let result = retry_durations::retry_async_with(sleeper.clone(), strategy, || async {
+///     Err::<(), _>("failed")
+/// })
+/// .await;
+/// assert_eq!(result.unwrap_err().errors().len(), 4);
+/// assert_eq!(sleeper.slept().len(), 3);
+/// # }
+/// ```
+pub async fn retry_async_with<T, E, S, B, F, Fut>(
+    sleeper: S,
+    strategy: B,
+    op: F,
+) -> Result<T, RetryError<E>>
+where
+    S: Sleeper,
+    B: Backoff,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_async_if_with(sleeper, strategy, |_: &E| Decision::Retry, op).await
+}
+
+/// Run async `op` until it succeeds, awaiting `sleeper` of each duration of `strategy`
+/// between attempts as `classify` decides on each error.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations
+/// or `classify` decides to stop.
+pub async fn retry_async_if_with<T, E, S, B, C, F, Fut>(
+    sleeper: S,
+    mut strategy: B,
+    mut classify: C,
+    mut op: F,
+) -> Result<T, RetryError<E>>
+where
+    S: Sleeper,
+    B: Backoff,
+    C: Classify<E>,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut errors = Vec::new();
+    loop {
+        let decision = match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let decision = classify.classify(&e);
+                errors.push(e);
+                decision
+            }
+        };
+        let attempt = u32::try_from(errors.len() - 1).unwrap_or(u32::MAX);
+        match decision.next_duration(&mut strategy, attempt) {
+            Some(duration) => sleeper.sleep(duration).await,
+            None => return Err(RetryError::new(errors)),
+        }
+    }
+}
+
+/// Run async `op` until it succeeds, awaiting `tokio::time::sleep` of each duration of `strategy`
+/// between attempts.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::time::Duration;
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_millis(10))
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// let result = retry_durations::retry_async(strategy, || async { Ok::<_, ()>(42) }).await;
+/// assert_eq!(result, Ok(42));
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn retry_async<T, E, B, F, Fut>(strategy: B, op: F) -> Result<T, RetryError<E>>
+where
+    B: Backoff,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_async_with(crate::TokioSleeper, strategy, op).await
+}
+
+/// Run async `op` until it succeeds, awaiting `tokio::time::sleep` of each duration of `strategy`
+/// between attempts as `classify` decides on each error.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations
+/// or `classify` decides to stop.
+#[cfg(feature = "tokio")]
+pub async fn retry_async_if<T, E, B, C, F, Fut>(
+    strategy: B,
+    classify: C,
+    op: F,
+) -> Result<T, RetryError<E>>
+where
+    B: Backoff,
+    C: Classify<E>,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_async_if_with(crate::TokioSleeper, strategy, classify, op).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::{HintPolicy, ManualSleeper};
+
+    fn strategy() -> crate::Strategy {
+        crate::builder()
+            .duration(Duration::from_secs(1))
+            .jitter(0.0)
+            .max_retries(3)
+            .build()
+            .unwrap()
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn it_sleeps_between_attempts() {
+        let start = Instant::now();
+        let mut count = 0;
+        let result: Result<(), _> = retry_async(strategy(), || {
+            count += 1;
+            let attempt = count;
+            async move { Err(attempt) }
+        })
+        .await;
+        assert_eq!(result.unwrap_err().into_errors(), [1, 2, 3, 4]);
+        assert_eq!(start.elapsed(), Duration::from_secs(1 + 2 + 4));
+    }
+
+    #[tokio::test]
+    async fn it_uses_sleeper() {
+        let sleeper = ManualSleeper::new();
+        let start = Instant::now();
+        let mut count = 0;
+        let result = retry_async_with(sleeper.clone(), strategy(), || {
+            count += 1;
+            let attempt = count;
+            async move {
+                if attempt < 3 {
+                    Err(attempt)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            sleeper.slept(),
+            [Duration::from_secs(1), Duration::from_secs(2)]
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn it_classifies_errors() {
+        let sleeper = ManualSleeper::new();
+        let mut count = 0;
+        let result: Result<(), _> = retry_async_if_with(
+            sleeper.clone(),
+            strategy(),
+            |&e: &u32| match e {
+                1 => Decision::RetryAfter(Duration::from_secs(30)),
+                _ => Decision::Stop,
+            },
+            || {
+                count += 1;
+                let attempt = count;
+                async move { Err(attempt) }
+            },
+        )
+        .await;
+        assert_eq!(result.unwrap_err().into_errors(), [1, 2]);
+        assert_eq!(sleeper.slept(), [Duration::from_secs(30)]);
+    }
+
+    #[tokio::test]
+    async fn it_applies_hint_policy() {
+        let strategy = crate::builder()
+            .duration(Duration::from_secs(1))
+            .duration_max(Duration::from_secs(10))
+            .jitter(0.0)
+            .max_retries(3)
+            .hint_policy(HintPolicy::Clamp)
+            .build()
+            .unwrap();
+        let sleeper = ManualSleeper::new();
+        let mut count = 0;
+        let result: Result<(), _> = retry_async_if_with(
+            sleeper.clone(),
+            strategy,
+            |&e: &u32| match e {
+                1 => Decision::RetryAfter(Duration::from_secs(30)),
+                _ => Decision::Retry,
+            },
+            || {
+                count += 1;
+                let attempt = count;
+                async move { Err(attempt) }
+            },
+        )
+        .await;
+        assert_eq!(result.unwrap_err().into_errors(), [1, 2, 3, 4]);
+        assert_eq!(
+            sleeper.slept(),
+            [
+                Duration::from_secs(10),
+                Duration::from_secs(2),
+                Duration::from_secs(4)
+            ]
+        );
+    }
+}
@@ -1,12 +1,46 @@
 //! This library generates a duration iterator for [retry](/retry/) crates.
 
-use std::time::Duration;
+use std::{fmt, iter::FusedIterator, time::Duration};
 
 use derive_builder::Builder;
 use fastrand::Rng;
 
-#[doc(hidden)]
-#[derive(Debug, Builder)]
+mod auto_reset;
+mod classified;
+#[cfg(feature = "serde")]
+mod config;
+mod future;
+mod retry;
+mod retry_after;
+mod sleeper;
+#[cfg(feature = "futures")]
+mod stream;
+
+pub use auto_reset::AutoReset;
+pub use classified::{Classified, UnknownClass};
+#[cfg(feature = "serde")]
+pub use config::{Config, ConfigKind};
+#[cfg(feature = "tokio")]
+pub use future::{retry_async, retry_async_if};
+pub use future::{retry_async_if_with, retry_async_with};
+pub use retry::{retry, retry_if, Classify, Decision, RetryError};
+pub use retry_after::parse_retry_after;
+#[cfg(feature = "async-std")]
+pub use sleeper::AsyncStdSleeper;
+#[cfg(feature = "tokio")]
+pub use sleeper::TokioSleeper;
+pub use sleeper::{ManualSleeper, Sleeper};
+#[cfg(feature = "smol")]
+pub use sleeper::{SmolSleep, SmolSleeper};
+#[cfg(feature = "futures")]
+pub use stream::{Retrying, Ticker};
+
+/// An iterator of durations between retries, built by [`builder`].
+///
+/// A clone repeats the same jitter as the original one. Use [`Strategy::fork`] for
+/// a copy with different jitter.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate", error = "BuildError"))]
 pub struct Strategy {
     /// Set initial duration.
     ///
@@ -20,24 +54,88 @@ pub struct Strategy {
     #[builder(setter(into), default)]
     duration_max: Option<Duration>,
 
+    /// Set min durations, applied after jitter.
+    ///
+    /// Default is no min duration limits.
+    #[builder(setter(into), default)]
+    duration_min: Option<Duration>,
+
     #[doc(hidden)]
     #[builder(field(private), default)]
     kind: Kind,
 
-    /// Set a duration jitter ratio.
+    /// Set a growth factor of exponential interval strategy.
     ///
-    /// Default is `0.1`.
-    #[builder(default = "0.1")]
-    jitter: f32,
+    /// Default is `2.0`.
+    #[builder(default = "2.0")]
+    multiplier: f64,
 
-    #[doc(hidden)]
-    #[builder(field(private), default)]
+    /// Set a duration jitter mode. A `f32` value is taken as a jitter ratio.
+    ///
+    /// Default is `Jitter::Ratio(0.1)`.
+    #[builder(setter(into), default = "Jitter::Ratio(0.1)")]
+    jitter: Jitter,
+
+    /// Set max total of durations. The iterator ends when the next duration exceeds it.
+    ///
+    /// Default is no max total duration limits.
+    #[builder(setter(into), default)]
+    max_elapsed: Option<Duration>,
+
+    /// Set whether the last duration is truncated to fit in max total of durations,
+    /// instead of ending the iterator.
+    ///
+    /// Default is `false`.
+    #[builder(default)]
+    truncate_elapsed: bool,
+
+    /// Set max retry count, which makes the iterator finite.
+    ///
+    /// Default is no max retry count limits.
+    #[builder(setter(into), default)]
+    max_retries: Option<u32>,
+
+    /// Set how a server provided hint by `Strategy::retry_after` is applied.
+    ///
+    /// Default is `HintPolicy::Hint`.
+    #[builder(default)]
+    hint_policy: HintPolicy,
+
+    /// Set a random number generator for jitter.
+    ///
+    /// Default is randomly seeded one.
+    #[builder(default)]
     rng: Rng,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    attempt: u32,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    current: Duration,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    previous: Duration,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    elapsed: Duration,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    exhausted: bool,
+
+    #[doc(hidden)]
+    #[builder(setter(skip))]
+    hint: Option<Duration>,
 }
 
 /// Create a new Strategy builder.
 ///
-/// A built iterator has infinite items, so you may want to `take()` for finite retry count.
+/// A built iterator has infinite items unless `max_retries` or `max_elapsed` is set,
+/// so you may want to `take()` for finite retry count.
 ///
 /// # Examples
 ///
@@ -55,14 +153,273 @@ pub fn builder() -> StrategyBuilder {
     StrategyBuilder::default()
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// An error on building a strategy from invalid settings.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// Initial duration is zero.
+    ZeroDuration,
+    /// Max duration is less than initial duration.
+    DurationMaxLessThanDuration {
+        duration: Duration,
+        duration_max: Duration,
+    },
+    /// Min duration is greater than max duration.
+    DurationMinGreaterThanMax {
+        duration_min: Duration,
+        duration_max: Duration,
+    },
+    /// Jitter ratio is not in `0.0..=1.0`.
+    InvalidJitter(f32),
+    /// Multiplier is not a finite value of at least `1.0`.
+    InvalidMultiplier(f64),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::ZeroDuration => write!(f, "duration must not be zero"),
+            BuildError::DurationMaxLessThanDuration {
+                duration,
+                duration_max,
+            } => write!(
+                f,
+                "duration_max {duration_max:?} must not be less than duration {duration:?}"
+            ),
+            BuildError::DurationMinGreaterThanMax {
+                duration_min,
+                duration_max,
+            } => write!(
+                f,
+                "duration_min {duration_min:?} must not be greater than duration_max {duration_max:?}"
+            ),
+            BuildError::InvalidJitter(ratio) => {
+                write!(f, "jitter ratio {ratio} must be in 0.0..=1.0")
+            }
+            BuildError::InvalidMultiplier(multiplier) => {
+                write!(f, "multiplier {multiplier} must be finite and at least 1.0")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// A schedule of durations between retries.
+///
+/// Implement this trait to use a custom schedule with [`StrategyBuilder::backoff`],
+/// where jitter and max duration are still applied on each duration.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use retry_durations::Backoff;
+///
+/// #[derive(Clone)]
+/// struct Steps;
+///
+/// impl Backoff for Steps {
+///     fn next_delay(&mut self, attempt: u32) -> Option<Duration> {
+///         [1, 5, 30].get(attempt as usize).map(|&s| Duration::from_secs(s))
+///     }
+///
+///     fn reset(&mut self) {}
+/// }
+///
+/// let xs: Vec<_> = retry_durations::builder()
+///     .backoff(Steps)
+///     .jitter(0.0)
+///     .build()
+///     .unwrap()
+///     .collect();
+/// assert_eq!(xs.len(), 3);
+/// ```
+pub trait Backoff {
+    /// Return a duration before the retry of `attempt`, counting from zero,
+    /// or `None` to stop retrying.
+    ///
+    /// Callers pass consecutive attempts from zero, and from zero again after `reset`,
+    /// so a sequential implementation may ignore `attempt`.
+    fn next_delay(&mut self, attempt: u32) -> Option<Duration>;
+
+    /// Return a duration before the retry of `attempt` like `next_delay`,
+    /// overridden by a server provided `hint`, such as `Retry-After` header.
+    ///
+    /// The default implementation advances the schedule, and returns `hint` as is.
+    fn next_delay_with_hint(&mut self, attempt: u32, hint: Duration) -> Option<Duration> {
+        self.next_delay(attempt).map(|_| hint)
+    }
+
+    /// Restart the schedule from the beginning.
+    fn reset(&mut self);
+}
+
+impl<B: Backoff + ?Sized> Backoff for &mut B {
+    fn next_delay(&mut self, attempt: u32) -> Option<Duration> {
+        (**self).next_delay(attempt)
+    }
+
+    fn next_delay_with_hint(&mut self, attempt: u32, hint: Duration) -> Option<Duration> {
+        (**self).next_delay_with_hint(attempt, hint)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+trait CustomBackoff: Backoff + Send + Sync {
+    fn clone_box(&self) -> Box<dyn CustomBackoff>;
+}
+
+impl<B: Backoff + Clone + Send + Sync + 'static> CustomBackoff for B {
+    fn clone_box(&self) -> Box<dyn CustomBackoff> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CustomBackoff> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl fmt::Debug for dyn CustomBackoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Backoff")
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 enum Kind {
     Fixed,
     #[default]
     Exponential,
+    Linear(Duration),
+    Fibonacci,
+    Decorrelated,
+    Custom(Box<dyn CustomBackoff>),
+}
+
+/// Jitter mode applied on each duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Jitter {
+    /// Uniformly random between zero and the duration.
+    Full,
+    /// Half of the duration plus uniformly random between zero and the other half.
+    Equal,
+    /// Uniformly random within plus or minus the ratio of the duration.
+    Ratio(f32),
+}
+
+impl Jitter {
+    /// Return min, max and mean of jittered `d` as `WaitBounds`.
+    fn bounds(&self, d: Duration) -> WaitBounds {
+        match *self {
+            Jitter::Full => WaitBounds {
+                min: Duration::ZERO,
+                max: d,
+                expected: d / 2,
+            },
+            Jitter::Equal => {
+                let half = d / 2;
+                WaitBounds {
+                    min: half,
+                    max: d,
+                    expected: half + (d - half) / 2,
+                }
+            }
+            Jitter::Ratio(ratio) => {
+                let j = saturating_mul_f64(d, ratio as f64);
+                WaitBounds {
+                    min: d.saturating_sub(j),
+                    max: d.saturating_add(j),
+                    expected: d,
+                }
+            }
+        }
+    }
+}
+
+/// Bounds of total durations, returned by `Strategy::wait_bounds`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WaitBounds {
+    /// Minimum total duration.
+    pub min: Duration,
+    /// Maximum total duration.
+    pub max: Duration,
+    /// Expected total duration.
+    pub expected: Duration,
+}
+
+impl From<f32> for Jitter {
+    fn from(ratio: f32) -> Self {
+        Jitter::Ratio(ratio)
+    }
+}
+
+/// A policy how a server provided hint, such as `Retry-After` header, overrides the next duration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum HintPolicy {
+    /// Use the hint as is.
+    #[default]
+    Hint,
+    /// Use the longer one of the hint and the next duration.
+    Max,
+    /// Use the hint clamped to min and max durations.
+    Clamp,
 }
 
 impl StrategyBuilder {
+    fn validate(&self) -> Result<(), BuildError> {
+        if !matches!(self.kind, Some(Kind::Custom(_))) {
+            let duration = self.duration.unwrap_or(Duration::from_secs(2));
+            if duration.is_zero() {
+                return Err(BuildError::ZeroDuration);
+            }
+            if let Some(Some(duration_max)) = self.duration_max {
+                if duration_max < duration {
+                    return Err(BuildError::DurationMaxLessThanDuration {
+                        duration,
+                        duration_max,
+                    });
+                }
+            }
+        }
+        if let (Some(Some(duration_min)), Some(Some(duration_max))) =
+            (self.duration_min, self.duration_max)
+        {
+            if duration_max < duration_min {
+                return Err(BuildError::DurationMinGreaterThanMax {
+                    duration_min,
+                    duration_max,
+                });
+            }
+        }
+        if let Some(Jitter::Ratio(ratio)) = self.jitter {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(BuildError::InvalidJitter(ratio));
+            }
+        }
+        if let Some(multiplier) = self.multiplier {
+            if !(multiplier.is_finite() && 1.0 <= multiplier) {
+                return Err(BuildError::InvalidMultiplier(multiplier));
+            }
+        }
+        Ok(())
+    }
+
     /// Select fixed interval strategy.
     pub fn fixed(&mut self) -> &mut Self {
         self.kind = Some(Kind::Fixed);
@@ -74,39 +431,371 @@ impl StrategyBuilder {
         self.kind = Some(Kind::Exponential);
         self
     }
+
+    /// Select linear interval strategy, which adds `step` to the duration on each attempt.
+    pub fn linear<D: Into<Duration>>(&mut self, step: D) -> &mut Self {
+        self.kind = Some(Kind::Linear(step.into()));
+        self
+    }
+
+    /// Select fibonacci interval strategy, where each duration is the sum of the previous two.
+    pub fn fibonacci(&mut self) -> &mut Self {
+        self.kind = Some(Kind::Fibonacci);
+        self
+    }
+
+    /// Select decorrelated jitter strategy.
+    ///
+    /// Each duration is a random value between initial duration and three times the previous one,
+    /// capped by max duration. This strategy has its own randomness, so jitter is not applied.
+    pub fn decorrelated(&mut self) -> &mut Self {
+        self.kind = Some(Kind::Decorrelated);
+        self
+    }
+
+    /// Seed the random number generator for jitter, which makes durations reproducible.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.rng = Some(Rng::with_seed(seed));
+        self
+    }
+
+    /// Seed the random number generator for jitter by hashing `key`, such as a hostname.
+    ///
+    /// The same key always makes the same durations, across restarts and builds,
+    /// while different keys make decorrelated ones.
+    pub fn seed_key<K: AsRef<[u8]>>(&mut self, key: K) -> &mut Self {
+        self.seed(fnv1a(key.as_ref()))
+    }
+
+    /// Select a custom strategy.
+    pub fn backoff<B: Backoff + Clone + Send + Sync + 'static>(&mut self, backoff: B) -> &mut Self {
+        self.kind = Some(Kind::Custom(Box::new(backoff)));
+        self
+    }
 }
 
 impl Kind {
-    pub fn next(&self, durration: Duration) -> Duration {
+    pub fn next(&self, durration: Duration, previous: Duration, multiplier: f64) -> Duration {
         match self {
-            Kind::Fixed => durration,
-            Kind::Exponential => durration.saturating_mul(2),
+            Kind::Fixed | Kind::Decorrelated | Kind::Custom(_) => durration,
+            Kind::Exponential => saturating_mul_f64(durration, multiplier),
+            Kind::Linear(step) => durration.saturating_add(*step),
+            Kind::Fibonacci => durration.saturating_add(previous),
         }
     }
 }
 
+/// Number of fractional bits used to multiply a `Duration` by a `f64` in fixed point.
+const FRACTION_BITS: u32 = 32;
+
+/// Multiply `d` by `factor` on integer nanoseconds, saturating at `Duration::MAX`.
+///
+/// Negative and NaN factors are treated as zero.
+fn saturating_mul_f64(d: Duration, factor: f64) -> Duration {
+    let factor = (factor * (1u64 << FRACTION_BITS) as f64).round() as u128;
+    d.as_nanos()
+        .checked_mul(factor)
+        .map_or(Duration::MAX, |n| saturating_from_nanos(n >> FRACTION_BITS))
+}
+
+/// 64-bit FNV-1a hash, which is stable unlike `std::hash::DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Convert nanoseconds into a `Duration`, saturating at `Duration::MAX`.
+fn saturating_from_nanos(nanos: u128) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    u64::try_from(nanos / NANOS_PER_SEC).map_or(Duration::MAX, |secs| {
+        Duration::new(secs, (nanos % NANOS_PER_SEC) as u32)
+    })
+}
+
 impl Strategy {
+    /// Restart the schedule from the initial duration.
+    ///
+    /// The random number generator for jitter keeps its state, so jitter is not repeated.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.current = Duration::ZERO;
+        self.previous = Duration::ZERO;
+        self.elapsed = Duration::ZERO;
+        self.exhausted = false;
+        self.hint = None;
+        if let Kind::Custom(backoff) = &mut self.kind {
+            backoff.reset();
+        }
+    }
+
+    /// Return a copy of this strategy with a random number generator forked from this one,
+    /// so that each copy of a template has different jitter.
+    ///
+    /// Forks of a seeded strategy are still reproducible.
+    pub fn fork(&mut self) -> Strategy {
+        let mut strategy = self.clone();
+        strategy.rng = self.rng.fork();
+        strategy
+    }
+
     fn j(&mut self, d: Duration) -> Duration {
-        let j = (d.as_secs_f32() * self.jitter * 1000.0) as i32;
-        let j = self.rng.i32((-j)..(j + 1));
-        if 0 <= j {
-            d.saturating_add(Duration::from_millis(j as u64))
-        } else {
-            d.saturating_sub(Duration::from_millis((-j) as u64))
+        match self.jitter {
+            Jitter::Full => saturating_from_nanos(self.rng.u128(0..=d.as_nanos())),
+            Jitter::Equal => {
+                let half = d / 2;
+                half + saturating_from_nanos(self.rng.u128(0..=(d - half).as_nanos()))
+            }
+            Jitter::Ratio(ratio) => {
+                let j = saturating_mul_f64(d, ratio as f64).as_nanos();
+                let d = d.as_nanos();
+                saturating_from_nanos(self.rng.u128(d.saturating_sub(j)..=d + j))
+            }
         }
     }
 
-    fn update_duration(&mut self) -> Duration {
+    fn decorrelated(&mut self) -> Duration {
+        let base = self.duration;
+        let upper = self.previous.max(base).saturating_mul(3);
+        let sleep = saturating_from_nanos(self.rng.u128(base.as_nanos()..=upper.as_nanos()));
+        let sleep = self.duration_max.map_or(sleep, |cap| sleep.min(cap));
+        self.previous = sleep;
+        sleep
+    }
+
+    fn saturate(&self, d: Duration) -> Duration {
+        self.duration_max.map_or(d, |saturation| d.min(saturation))
+    }
+
+    fn clamp(&self, d: Duration) -> Duration {
+        self.saturate(self.duration_min.map_or(d, |floor| d.max(floor)))
+    }
+
+    /// Return the duration before jitter of `attempt` without iterating,
+    /// or `None` if the strategy has no such form.
+    fn base_duration(&self, attempt: u32) -> Option<Duration> {
         let duration = self.duration;
-        let next_duration = self.kind.next(duration);
+        let duration = match self.kind {
+            Kind::Fixed => duration,
+            Kind::Exponential => saturating_mul_f64(duration, self.multiplier.powf(attempt as f64)),
+            Kind::Linear(step) => duration.saturating_add(step.saturating_mul(attempt)),
+            Kind::Fibonacci => {
+                let (mut previous, mut duration) = (Duration::ZERO, duration);
+                for _ in 0..attempt {
+                    let next_duration = self.saturate(duration.saturating_add(previous));
+                    if next_duration == duration && !previous.is_zero() {
+                        break;
+                    }
+                    (previous, duration) = (duration, next_duration);
+                }
+                duration
+            }
+            Kind::Decorrelated | Kind::Custom(_) => return None,
+        };
+        Some(self.saturate(duration))
+    }
+
+    fn may_end_early(&self) -> bool {
+        matches!(self.kind, Kind::Custom(_)) || self.max_elapsed.is_some()
+    }
+
+    /// Return the exact number of remaining durations.
+    ///
+    /// Returns `None` unless the count is known in advance, that is, the iterator has ended,
+    /// or `max_retries` is set and neither `max_elapsed` nor a custom strategy is set.
+    pub fn remaining(&self) -> Option<usize> {
+        if self.exhausted {
+            return Some(0);
+        }
+        let max = self.max_retries?;
+        if self.may_end_early() {
+            return None;
+        }
+        Some(max.saturating_sub(self.attempt) as usize)
+    }
+
+    /// Return a duration of `attempt`, counting from zero, with jitter applied.
+    ///
+    /// This does not change the schedule, and takes constant time for fixed,
+    /// exponential and linear strategies, so a schedule can be resumed from a persisted attempt count.
+    /// Returns `None` when `attempt` exceeds max retry count, or the strategy is decorrelated or custom one.
+    pub fn delay_for_attempt(&mut self, attempt: u32) -> Option<Duration> {
+        if self.max_retries.is_some_and(|max| max <= attempt) {
+            return None;
+        }
+        let duration = self.base_duration(attempt)?;
+        let duration = self.j(duration);
+        Some(self.clamp(duration))
+    }
 
-        if let Some(saturation) = self.duration_max {
-            self.duration = next_duration.min(saturation);
-            self.j(duration).min(saturation)
+    /// Return `duration` fitted in the rest of max total of durations after `elapsed`,
+    /// or `None` if it does not fit.
+    fn fit_elapsed(&self, elapsed: Duration, duration: Duration) -> Option<Duration> {
+        let Some(max_elapsed) = self.max_elapsed else {
+            return Some(duration);
+        };
+        let remaining = max_elapsed.saturating_sub(elapsed);
+        if duration <= remaining {
+            Some(duration)
+        } else if self.truncate_elapsed && !remaining.is_zero() {
+            Some(remaining)
         } else {
-            self.duration = next_duration;
-            self.j(duration)
+            None
+        }
+    }
+
+    fn spend(&mut self, duration: Duration) -> Option<Duration> {
+        let duration = self.fit_elapsed(self.elapsed, duration)?;
+        self.elapsed = self.elapsed.saturating_add(duration);
+        Some(duration)
+    }
+
+    fn update_duration(&mut self) -> Option<Duration> {
+        if self.exhausted {
+            return None;
         }
+        let duration = self.next_duration().and_then(|d| self.spend(d));
+        self.exhausted = duration.is_none();
+        duration
+    }
+
+    fn next_duration(&mut self) -> Option<Duration> {
+        let duration = self.next_base()?;
+        let duration = match self.kind {
+            Kind::Decorrelated => duration,
+            _ => self.j(duration),
+        };
+        let duration = self.clamp(duration);
+        Some(match (self.hint.take(), self.hint_policy) {
+            (None, _) => duration,
+            (Some(hint), HintPolicy::Hint) => hint,
+            (Some(hint), HintPolicy::Max) => hint.max(duration),
+            (Some(hint), HintPolicy::Clamp) => self.clamp(hint),
+        })
+    }
+
+    /// Override the next duration by a server provided hint, such as `Retry-After` header,
+    /// as `hint_policy` of the builder.
+    ///
+    /// The schedule still advances, and max retry count and max total duration are still applied.
+    /// See [`parse_retry_after`] to parse the header.
+    pub fn retry_after(&mut self, hint: Duration) {
+        self.hint = Some(hint);
+    }
+
+    /// Advance the schedule and return the next duration before jitter and clamping.
+    fn next_base(&mut self) -> Option<Duration> {
+        let attempt = self.attempt;
+        if self.max_retries.is_some_and(|max| max <= attempt) {
+            return None;
+        }
+        self.attempt = attempt.saturating_add(1);
+
+        if let Kind::Custom(backoff) = &mut self.kind {
+            return backoff.next_delay(attempt);
+        }
+        if let Kind::Decorrelated = self.kind {
+            return Some(self.decorrelated());
+        }
+
+        let duration = match self.kind {
+            // Computed from attempt to agree with `delay_for_attempt` without rounding each step.
+            Kind::Exponential => self.base_duration(attempt)?,
+            _ if attempt == 0 => self.duration,
+            _ => self.current,
+        };
+        let next_duration = self.kind.next(duration, self.previous, self.multiplier);
+        self.previous = duration;
+        self.current = self.saturate(next_duration);
+        Some(duration)
+    }
+
+    /// Return the schedule from the beginning without jitter.
+    ///
+    /// Decorrelated jitter strategy is still random, since randomness is the schedule itself.
+    pub fn base_durations(&self) -> Strategy {
+        let mut strategy = self.clone();
+        strategy.reset();
+        strategy.jitter = Jitter::Ratio(0.0);
+        strategy
+    }
+
+    /// Return bounds of total durations of the first `attempts` retries from the beginning.
+    ///
+    /// Expected total is computed from the mean of jitter, regardless of min and max durations
+    /// clipping the distribution. For decorrelated jitter strategy, each expected duration is
+    /// approximated from the previous expected one. Each total ends or is truncated at
+    /// max total of durations, as the iterator does.
+    pub fn wait_bounds(&self, attempts: usize) -> WaitBounds {
+        let mut strategy = self.clone();
+        strategy.reset();
+        // Each of min, max and expected totals, and whether it has ended at max total.
+        let mut totals = [(Duration::ZERO, false); 3];
+        let mut previous = WaitBounds {
+            min: self.duration,
+            max: self.duration,
+            expected: self.duration,
+        };
+        for _ in 0..attempts {
+            let bounds = if let Kind::Decorrelated = strategy.kind {
+                if strategy
+                    .max_retries
+                    .is_some_and(|max| max <= strategy.attempt)
+                {
+                    break;
+                }
+                strategy.attempt += 1;
+                let base = strategy.duration;
+                WaitBounds {
+                    min: base,
+                    max: previous.max.max(base).saturating_mul(3),
+                    expected: base.saturating_add(previous.expected.max(base).saturating_mul(3))
+                        / 2,
+                }
+            } else {
+                let Some(duration) = strategy.next_base() else {
+                    break;
+                };
+                strategy.jitter.bounds(duration)
+            };
+            previous = WaitBounds {
+                min: strategy.clamp(bounds.min),
+                max: strategy.clamp(bounds.max),
+                expected: strategy.clamp(bounds.expected),
+            };
+            let durations = [previous.min, previous.max, previous.expected];
+            for ((total, ended), duration) in totals.iter_mut().zip(durations) {
+                if *ended {
+                    continue;
+                }
+                match strategy.fit_elapsed(*total, duration) {
+                    Some(duration) => *total = total.saturating_add(duration),
+                    None => *ended = true,
+                }
+            }
+        }
+        let [(min, _), (max, _), (expected, _)] = totals;
+        WaitBounds { min, max, expected }
+    }
+}
+
+/// `Strategy` is sequential, so `attempt` is ignored and the next duration of the schedule
+/// is returned. Use [`Strategy::delay_for_attempt`] for a duration of a specific attempt.
+impl Backoff for Strategy {
+    fn next_delay(&mut self, _attempt: u32) -> Option<Duration> {
+        self.update_duration()
+    }
+
+    /// The hint is applied as `hint_policy` of the builder.
+    fn next_delay_with_hint(&mut self, _attempt: u32, hint: Duration) -> Option<Duration> {
+        self.retry_after(hint);
+        self.update_duration()
+    }
+
+    fn reset(&mut self) {
+        Strategy::reset(self);
     }
 }
 
@@ -114,14 +803,59 @@ impl Iterator for Strategy {
     type Item = Duration;
 
     fn next(&mut self) -> Option<Self::Item> {
-        Some(self.update_duration())
+        self.update_duration()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        let attempt = self
+            .attempt
+            .saturating_add(u32::try_from(n).unwrap_or(u32::MAX));
+        let current = match self.base_duration(attempt) {
+            Some(current) if self.max_elapsed.is_none() => current,
+            _ => {
+                for _ in 0..n {
+                    self.next()?;
+                }
+                return self.next();
+            }
+        };
+        if let Some(max) = self.max_retries {
+            if max <= attempt {
+                self.attempt = self.attempt.max(max);
+                self.exhausted = true;
+                return None;
+            }
+        }
+        self.attempt = attempt;
+        self.current = current;
+        self.previous = attempt
+            .checked_sub(1)
+            .and_then(|attempt| self.base_duration(attempt))
+            .unwrap_or_default();
+        self.next()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (usize::MAX, None)
+        if let Some(remaining) = self.remaining() {
+            return (remaining, Some(remaining));
+        }
+        let upper = self
+            .max_retries
+            .map(|max| max.saturating_sub(self.attempt) as usize);
+        match upper {
+            Some(upper) => (0, Some(upper)),
+            None if self.may_end_early() => (0, None),
+            None => (usize::MAX, None),
+        }
     }
 }
 
+/// Once the iterator returns `None`, it keeps returning `None` until `reset`.
+impl FusedIterator for Strategy {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +884,567 @@ mod tests {
             println!("{x:?}");
         }
     }
+
+    #[test]
+    fn linear() {
+        let xs: Vec<_> = builder()
+            .duration(Duration::from_secs(1))
+            .linear(Duration::from_millis(500))
+            .duration_max(Duration::from_secs(2))
+            .jitter(0.0)
+            .build()
+            .unwrap()
+            .take(4)
+            .collect();
+        assert_eq!(
+            xs,
+            [
+                Duration::from_millis(1000),
+                Duration::from_millis(1500),
+                Duration::from_millis(2000),
+                Duration::from_millis(2000),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiplier() {
+        let xs: Vec<_> = builder()
+            .duration(Duration::from_millis(100))
+            .multiplier(1.5)
+            .jitter(0.0)
+            .build()
+            .unwrap()
+            .take(4)
+            .collect();
+        assert_eq!(
+            xs,
+            [
+                Duration::from_millis(100),
+                Duration::from_millis(150),
+                Duration::from_millis(225),
+                Duration::from_micros(337_500),
+            ]
+        );
+
+        assert_eq!(saturating_mul_f64(Duration::MAX, 3.0), Duration::MAX);
+        assert_eq!(
+            saturating_mul_f64(Duration::from_secs(1), -1.0),
+            Duration::ZERO
+        );
+        assert_eq!(
+            saturating_mul_f64(Duration::new(1, 1), 3.0),
+            Duration::new(3, 3)
+        );
+    }
+
+    #[test]
+    fn fibonacci() {
+        let xs: Vec<_> = builder()
+            .duration(Duration::from_secs(1))
+            .fibonacci()
+            .duration_max(Duration::from_secs(6))
+            .jitter(0.0)
+            .build()
+            .unwrap()
+            .take(7)
+            .map(|x| x.as_secs())
+            .collect();
+        assert_eq!(xs, [1, 1, 2, 3, 5, 6, 6]);
+    }
+
+    #[test]
+    fn decorrelated() {
+        let base = Duration::from_millis(100);
+        let cap = Duration::from_secs(5);
+        let xs = builder()
+            .duration(base)
+            .duration_max(cap)
+            .decorrelated()
+            .build()
+            .unwrap();
+        let mut p = base;
+        for x in xs.take(100) {
+            assert!(base <= x);
+            assert!(x <= cap);
+            assert!(x <= p * 3);
+            p = x;
+        }
+    }
+
+    #[test]
+    fn jitter_modes() {
+        let d = Duration::from_secs(1);
+        for x in builder()
+            .duration(d)
+            .fixed()
+            .jitter(Jitter::Full)
+            .build()
+            .unwrap()
+            .take(100)
+        {
+            assert!(x <= d);
+        }
+        for x in builder()
+            .duration(d)
+            .fixed()
+            .jitter(Jitter::Equal)
+            .build()
+            .unwrap()
+            .take(100)
+        {
+            assert!(d / 2 <= x && x <= d);
+        }
+        for x in builder()
+            .duration(d)
+            .fixed()
+            .jitter(0.5)
+            .build()
+            .unwrap()
+            .take(100)
+        {
+            assert!(d / 2 <= x && x <= d * 3 / 2);
+        }
+    }
+
+    #[derive(Clone)]
+    struct Steps(Vec<Duration>, usize);
+
+    impl Backoff for Steps {
+        fn next_delay(&mut self, attempt: u32) -> Option<Duration> {
+            assert_eq!(attempt as usize, self.1);
+            self.1 += 1;
+            self.0.get(attempt as usize).copied()
+        }
+
+        fn reset(&mut self) {
+            self.1 = 0;
+        }
+    }
+
+    #[test]
+    fn custom_backoff() {
+        let steps = Steps(
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(10),
+                Duration::from_secs(3),
+            ],
+            0,
+        );
+        let mut xs = builder()
+            .backoff(steps)
+            .duration_max(Duration::from_secs(5))
+            .jitter(0.0)
+            .build()
+            .unwrap();
+        let expected = [
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            Duration::from_secs(3),
+        ];
+        assert_eq!(xs.by_ref().collect::<Vec<_>>(), expected);
+        assert_eq!(xs.next(), None);
+
+        xs.reset();
+        assert_eq!(xs.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn max_retries() {
+        let mut xs = builder().max_retries(3).build().unwrap();
+        assert_eq!(xs.size_hint(), (3, Some(3)));
+        assert_eq!(xs.remaining(), Some(3));
+        xs.next();
+        assert_eq!(xs.remaining(), Some(2));
+        assert_eq!(xs.by_ref().count(), 2);
+        assert_eq!(xs.remaining(), Some(0));
+        assert_eq!(xs.next(), None);
+
+        let xs = builder().build().unwrap();
+        assert_eq!(xs.remaining(), None);
+        assert_eq!(xs.size_hint(), (usize::MAX, None));
+
+        let xs = builder()
+            .max_retries(3)
+            .max_elapsed(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        assert_eq!(xs.remaining(), None);
+        assert_eq!(xs.size_hint(), (0, Some(3)));
+    }
+
+    #[test]
+    fn max_elapsed() {
+        let strategy = || {
+            let mut b = builder();
+            b.duration(Duration::from_secs(1))
+                .max_elapsed(Duration::from_secs(10))
+                .jitter(0.0);
+            b
+        };
+        let xs: Vec<_> = strategy().build().unwrap().map(|x| x.as_secs()).collect();
+        assert_eq!(xs, [1, 2, 4]);
+
+        let xs: Vec<_> = strategy()
+            .truncate_elapsed(true)
+            .build()
+            .unwrap()
+            .map(|x| x.as_secs())
+            .collect();
+        assert_eq!(xs, [1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn duration_min() {
+        let floor = Duration::from_millis(100);
+        for x in builder()
+            .duration(Duration::from_millis(120))
+            .fixed()
+            .jitter(Jitter::Full)
+            .duration_min(floor)
+            .build()
+            .unwrap()
+            .take(100)
+        {
+            assert!(floor <= x);
+        }
+    }
+
+    #[test]
+    fn jitter_precision() {
+        let d = Duration::from_micros(100);
+        let xs: Vec<_> = builder()
+            .duration(d)
+            .fixed()
+            .jitter(0.5)
+            .build()
+            .unwrap()
+            .take(100)
+            .collect();
+        assert!(xs.iter().all(|&x| d / 2 <= x && x <= d * 3 / 2));
+        assert!(xs.iter().any(|&x| x != d));
+
+        let d = Duration::from_secs(60 * 60 * 24 * 365);
+        let xs: Vec<_> = builder()
+            .duration(d)
+            .fixed()
+            .jitter(0.1)
+            .build()
+            .unwrap()
+            .take(100)
+            .collect();
+        assert!(xs.iter().all(|&x| d * 9 / 10 <= x && x <= d * 11 / 10));
+        assert!(xs.iter().any(|&x| x != d));
+    }
+
+    #[test]
+    fn seed() {
+        let xs = || -> Vec<_> { builder().seed(42).build().unwrap().take(10).collect() };
+        assert_eq!(xs(), xs());
+
+        let ys: Vec<_> = builder()
+            .rng(Rng::with_seed(42))
+            .build()
+            .unwrap()
+            .take(10)
+            .collect();
+        assert_eq!(xs(), ys);
+    }
+
+    #[test]
+    fn seed_key() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let xs =
+            |key: &str| -> Vec<_> { builder().seed_key(key).build().unwrap().take(10).collect() };
+        assert_eq!(xs("host-1"), xs("host-1"));
+        assert_ne!(xs("host-1"), xs("host-2"));
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            builder().duration(Duration::ZERO).build().unwrap_err(),
+            BuildError::ZeroDuration
+        );
+        assert_eq!(
+            builder()
+                .duration_max(Duration::from_secs(1))
+                .build()
+                .unwrap_err(),
+            BuildError::DurationMaxLessThanDuration {
+                duration: Duration::from_secs(2),
+                duration_max: Duration::from_secs(1),
+            }
+        );
+        assert_eq!(
+            builder()
+                .duration_min(Duration::from_secs(4))
+                .duration_max(Duration::from_secs(3))
+                .build()
+                .unwrap_err(),
+            BuildError::DurationMinGreaterThanMax {
+                duration_min: Duration::from_secs(4),
+                duration_max: Duration::from_secs(3),
+            }
+        );
+        assert_eq!(
+            builder().jitter(1.5).build().unwrap_err(),
+            BuildError::InvalidJitter(1.5)
+        );
+        assert!(matches!(
+            builder().jitter(f32::NAN).build().unwrap_err(),
+            BuildError::InvalidJitter(_)
+        ));
+        assert_eq!(
+            builder().multiplier(0.5).build().unwrap_err(),
+            BuildError::InvalidMultiplier(0.5)
+        );
+        assert!(builder().jitter(Jitter::Full).build().is_ok());
+    }
+
+    #[test]
+    fn reset() {
+        let mut xs = builder()
+            .duration(Duration::from_secs(1))
+            .jitter(0.0)
+            .build()
+            .unwrap();
+        let template = xs.clone();
+        assert_eq!(
+            xs.by_ref().take(3).map(|x| x.as_secs()).collect::<Vec<_>>(),
+            [1, 2, 4]
+        );
+        xs.reset();
+        assert_eq!(
+            xs.take(3).map(|x| x.as_secs()).collect::<Vec<_>>(),
+            [1, 2, 4]
+        );
+        assert_eq!(
+            template.take(3).map(|x| x.as_secs()).collect::<Vec<_>>(),
+            [1, 2, 4]
+        );
+
+        let mut xs = builder().seed(1).build().unwrap();
+        let first = xs.next();
+        xs.reset();
+        assert_ne!(xs.next(), first);
+    }
+
+    #[test]
+    fn delay_for_attempt() {
+        let strategies = [
+            builder().fixed().jitter(0.0).build().unwrap(),
+            builder().jitter(0.0).build().unwrap(),
+            builder()
+                .multiplier(1.5)
+                .duration_max(Duration::from_secs(30))
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+            builder()
+                .duration(Duration::from_millis(1234))
+                .multiplier(1.1)
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+            builder()
+                .linear(Duration::from_millis(300))
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+            builder()
+                .fibonacci()
+                .duration_max(Duration::from_secs(100))
+                .jitter(0.0)
+                .build()
+                .unwrap(),
+        ];
+        for strategy in strategies {
+            let xs: Vec<_> = strategy.clone().take(20).collect();
+            for (n, &x) in xs.iter().enumerate() {
+                assert_eq!(strategy.clone().delay_for_attempt(n as u32), Some(x));
+                assert_eq!(strategy.clone().nth(n), Some(x));
+            }
+            let mut ys = strategy.clone();
+            assert_eq!(ys.nth(3), Some(xs[3]));
+            assert_eq!(ys.nth(5), Some(xs[9]));
+            assert_eq!(ys.next(), Some(xs[10]));
+        }
+
+        let mut xs = builder().max_retries(5).build().unwrap();
+        assert_eq!(xs.delay_for_attempt(5), None);
+        assert_eq!(xs.nth(5), None);
+        assert_eq!(xs.next(), None);
+        assert_eq!(
+            builder()
+                .decorrelated()
+                .build()
+                .unwrap()
+                .delay_for_attempt(1),
+            None
+        );
+    }
+
+    #[test]
+    fn inspection() {
+        let strategy = builder()
+            .duration(Duration::from_secs(1))
+            .duration_max(Duration::from_secs(4))
+            .jitter(0.5)
+            .build()
+            .unwrap();
+        let xs: Vec<_> = strategy
+            .base_durations()
+            .take(4)
+            .map(|x| x.as_secs())
+            .collect();
+        assert_eq!(xs, [1, 2, 4, 4]);
+
+        assert_eq!(
+            strategy.wait_bounds(4),
+            WaitBounds {
+                min: Duration::from_millis(5500),
+                max: Duration::from_millis(12500),
+                expected: Duration::from_secs(11),
+            }
+        );
+
+        let strategy = builder()
+            .duration(Duration::from_secs(1))
+            .fixed()
+            .jitter(Jitter::Equal)
+            .max_retries(2)
+            .build()
+            .unwrap();
+        assert_eq!(
+            strategy.wait_bounds(10),
+            WaitBounds {
+                min: Duration::from_secs(1),
+                max: Duration::from_secs(2),
+                expected: Duration::from_millis(1500),
+            }
+        );
+
+        let strategy = builder()
+            .duration(Duration::from_secs(1))
+            .duration_max(Duration::from_secs(5))
+            .decorrelated()
+            .build()
+            .unwrap();
+        let bounds = strategy.wait_bounds(3);
+        assert_eq!(bounds.min, Duration::from_secs(3));
+        assert_eq!(bounds.max, Duration::from_secs(13));
+    }
+
+    #[test]
+    fn wait_bounds_max_elapsed() {
+        let strategy = |truncate_elapsed, jitter: f32| {
+            builder()
+                .duration(Duration::from_secs(1))
+                .jitter(jitter)
+                .max_elapsed(Duration::from_secs(10))
+                .truncate_elapsed(truncate_elapsed)
+                .build()
+                .unwrap()
+        };
+        let xs = strategy(false, 0.0);
+        assert_eq!(xs.clone().sum::<Duration>(), Duration::from_secs(7));
+        for attempts in [4, 10] {
+            assert_eq!(
+                xs.wait_bounds(attempts),
+                WaitBounds {
+                    min: Duration::from_secs(7),
+                    max: Duration::from_secs(7),
+                    expected: Duration::from_secs(7),
+                }
+            );
+        }
+        assert_eq!(
+            strategy(true, 0.0).wait_bounds(10),
+            WaitBounds {
+                min: Duration::from_secs(10),
+                max: Duration::from_secs(10),
+                expected: Duration::from_secs(10),
+            }
+        );
+        assert_eq!(
+            strategy(false, 0.5).wait_bounds(10),
+            WaitBounds {
+                min: Duration::from_millis(7500),
+                max: Duration::from_millis(4500),
+                expected: Duration::from_secs(7),
+            }
+        );
+    }
+
+    #[test]
+    fn retry_after() {
+        let strategy = |policy| {
+            builder()
+                .duration(Duration::from_secs(1))
+                .duration_max(Duration::from_secs(10))
+                .jitter(0.0)
+                .hint_policy(policy)
+                .build()
+                .unwrap()
+        };
+        let hinted = |policy, hint| {
+            let mut xs = strategy(policy);
+            xs.next();
+            xs.retry_after(hint);
+            let x = xs.next();
+            (x, xs.next())
+        };
+        let secs = Duration::from_secs;
+        assert_eq!(
+            hinted(HintPolicy::Hint, secs(30)),
+            (Some(secs(30)), Some(secs(4)))
+        );
+        assert_eq!(
+            hinted(HintPolicy::Max, secs(1)),
+            (Some(secs(2)), Some(secs(4)))
+        );
+        assert_eq!(
+            hinted(HintPolicy::Max, secs(3)),
+            (Some(secs(3)), Some(secs(4)))
+        );
+        assert_eq!(
+            hinted(HintPolicy::Clamp, secs(30)),
+            (Some(secs(10)), Some(secs(4)))
+        );
+    }
+
+    #[test]
+    fn max_elapsed_is_permanent() {
+        for seed in 0..100 {
+            let mut xs = builder()
+                .fixed()
+                .jitter(Jitter::Full)
+                .max_elapsed(Duration::from_secs(4))
+                .seed(seed)
+                .build()
+                .unwrap();
+            while xs.next().is_some() {}
+            assert_eq!(xs.remaining(), Some(0));
+            assert_eq!(xs.by_ref().take(100).count(), 0);
+            assert_eq!(xs.nth(2), None);
+
+            xs.reset();
+            assert!(xs.next().is_some());
+        }
+    }
+
+    #[test]
+    fn fork() {
+        let mut template = builder().seed(1).build().unwrap();
+        let a: Vec<_> = template.fork().take(10).collect();
+        let b: Vec<_> = template.fork().take(10).collect();
+        assert_ne!(a, b);
+
+        let mut template = builder().seed(1).build().unwrap();
+        assert_eq!(template.fork().take(10).collect::<Vec<_>>(), a);
+    }
 }
@@ -0,0 +1,231 @@
+use std::{error::Error, fmt, thread, time::Duration};
+
+use crate::Backoff;
+
+/// An error of all failed attempts, returned by [`retry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryError<E> {
+    errors: Vec<E>,
+}
+
+impl<E> RetryError<E> {
+    pub(crate) fn new(errors: Vec<E>) -> Self {
+        Self { errors }
+    }
+
+    /// Return errors of all attempts, in order.
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    /// Return the error of the last attempt.
+    pub fn last(&self) -> Option<&E> {
+        self.errors.last()
+    }
+
+    /// Unwrap errors of all attempts, in order.
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed after {} attempts", self.errors.len())?;
+        if let Some(last) = self.last() {
+            write!(f, ": {last}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Error + 'static> Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.last().map(|e| e as _)
+    }
+}
+
+/// A decision whether to retry on an error, made by [`Classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Retry after the next duration of the strategy.
+    Retry,
+    /// Stop retrying, without consuming the strategy.
+    Stop,
+    /// Retry after a server provided hint, such as `Retry-After` header.
+    ///
+    /// The hint is applied by [`Backoff::next_delay_with_hint`], which follows `hint_policy`
+    /// for `Strategy`. The next duration is still consumed, so the strategy limits retries.
+    RetryAfter(Duration),
+}
+
+impl Decision {
+    pub(crate) fn next_duration<B: Backoff>(
+        self,
+        backoff: &mut B,
+        attempt: u32,
+    ) -> Option<Duration> {
+        match self {
+            Decision::Retry => backoff.next_delay(attempt),
+            Decision::Stop => None,
+            Decision::RetryAfter(hint) => backoff.next_delay_with_hint(attempt, hint),
+        }
+    }
+}
+
+/// A classifier of errors, to distinguish transient errors from permanent ones.
+///
+/// This is implemented for closures of `FnMut(&E) -> Decision`.
+pub trait Classify<E> {
+    /// Decide whether to retry on `error`.
+    fn classify(&mut self, error: &E) -> Decision;
+}
+
+impl<E, F: FnMut(&E) -> Decision> Classify<E> for F {
+    fn classify(&mut self, error: &E) -> Decision {
+        self(error)
+    }
+}
+
+/// Run `op` until it succeeds, sleeping each duration of `strategy` between attempts.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_millis(10))
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// let mut count = 0;
+/// let result = retry_durations::retry(strategy, || {
+///     count += 1;
+///     if count < 3 { Err("not yet") } else { Ok(count) }
+/// });
+/// assert_eq!(result, Ok(3));
+/// ```
+pub fn retry<T, E, B, F>(strategy: B, op: F) -> Result<T, RetryError<E>>
+where
+    B: Backoff,
+    F: FnMut() -> Result<T, E>,
+{
+    retry_if(strategy, |_: &E| Decision::Retry, op)
+}
+
+/// Run `op` until it succeeds, sleeping each duration of `strategy` between attempts
+/// as `classify` decides on each error.
+///
+/// Returns the first success, or errors of all attempts when `strategy` has no more durations
+/// or `classify` decides to stop.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{io, time::Duration};
+/// use retry_durations::Decision;
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_millis(10))
+///     .build()
+///     .unwrap();
+/// let result: Result<(), _> = retry_durations::retry_if(
+///     strategy,
+///     |e: &io::Error| match e.kind() {
+///         io::ErrorKind::NotFound => Decision::Stop,
+///         _ => Decision::Retry,
+///     },
+///     || Err(io::Error::from(io::ErrorKind::NotFound)),
+/// );
+/// assert_eq!(result.unwrap_err().errors().len(), 1);
+/// ```
+pub fn retry_if<T, E, B, C, F>(
+    mut strategy: B,
+    mut classify: C,
+    mut op: F,
+) -> Result<T, RetryError<E>>
+where
+    B: Backoff,
+    C: Classify<E>,
+    F: FnMut() -> Result<T, E>,
+{
+    let mut errors = Vec::new();
+    loop {
+        let decision = match op() {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let decision = classify.classify(&e);
+                errors.push(e);
+                decision
+            }
+        };
+        let attempt = u32::try_from(errors.len() - 1).unwrap_or(u32::MAX);
+        match decision.next_duration(&mut strategy, attempt) {
+            Some(duration) => thread::sleep(duration),
+            None => return Err(RetryError::new(errors)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy() -> crate::Strategy {
+        crate::builder()
+            .duration(Duration::from_millis(1))
+            .fixed()
+            .max_retries(3)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_returns_all_errors() {
+        let mut count = 0;
+        let result: Result<(), _> = retry(strategy(), || {
+            count += 1;
+            Err(count)
+        });
+        let error = result.unwrap_err();
+        assert_eq!(error.errors(), [1, 2, 3, 4]);
+        assert_eq!(error.to_string(), "failed after 4 attempts: 4");
+    }
+
+    #[test]
+    fn it_returns_success() {
+        let mut count = 0;
+        let result = retry(strategy(), || {
+            count += 1;
+            if count < 2 {
+                Err(count)
+            } else {
+                Ok(count)
+            }
+        });
+        assert_eq!(result, Ok(2));
+    }
+
+    #[test]
+    fn it_classifies_errors() {
+        let mut strategy = strategy();
+        let mut count = 0;
+        let result: Result<(), _> = retry_if(
+            &mut strategy,
+            |&e: &u32| match e {
+                1 => Decision::RetryAfter(Duration::ZERO),
+                2 => Decision::Retry,
+                _ => Decision::Stop,
+            },
+            || {
+                count += 1;
+                Err(count)
+            },
+        );
+        assert_eq!(result.unwrap_err().errors(), [1, 2, 3]);
+        assert_eq!(strategy.remaining(), Some(1));
+    }
+}
@@ -0,0 +1,156 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Parse a value of `Retry-After` header, in either delay seconds or HTTP-date,
+/// into a duration from `now`.
+///
+/// A date in the past is a zero duration, and delay seconds too large saturate.
+/// Returns `None` if `value` is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use retry_durations::parse_retry_after;
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(784_111_747);
+/// assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+/// assert_eq!(
+///     parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT", now),
+///     Some(Duration::from_secs(30))
+/// );
+/// ```
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        // All digits fail to parse only on overflow.
+        return Some(Duration::from_secs(value.parse().unwrap_or(u64::MAX)));
+    }
+    let date = parse_http_date(value)?;
+    Some(date.duration_since(now).unwrap_or_default())
+}
+
+/// Parse HTTP-date of IMF-fixdate, obsolete RFC 850 and asctime formats.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let (year, month, day, time) = match value.split_once(',') {
+        Some((_, rest)) => match rest.split_whitespace().collect::<Vec<_>>()[..] {
+            // Sun, 06 Nov 1994 08:49:37 GMT
+            [day, month, year, time, "GMT"] => (year.parse().ok()?, month, day, time),
+            // Sunday, 06-Nov-94 08:49:37 GMT
+            [date, time, "GMT"] => {
+                let mut date = date.split('-');
+                let (day, month, year) = (date.next()?, date.next()?, date.next()?);
+                if year.len() != 2 || date.next().is_some() {
+                    return None;
+                }
+                let year: i64 = year.parse().ok()?;
+                let year = if year < 70 { 2000 + year } else { 1900 + year };
+                (year, month, day, time)
+            }
+            _ => return None,
+        },
+        // Sun Nov  6 08:49:37 1994
+        None => match value.split_whitespace().collect::<Vec<_>>()[..] {
+            [_, month, day, time, year] => (year.parse().ok()?, month, day, time),
+            _ => return None,
+        },
+    };
+    let month = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ]
+    .iter()
+    .position(|&m| m == month)? as i64
+        + 1;
+    let day: i64 = day.parse().ok()?;
+    let mut time = time.split(':').map(|x| x.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if time.next().is_some()
+        || !(1..=9999).contains(&year)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0..=60).contains(&second)
+    {
+        return None;
+    }
+    let secs = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    let offset = Duration::from_secs(secs.unsigned_abs());
+    if secs < 0 {
+        // A date before the platform can represent is still long past.
+        Some(UNIX_EPOCH.checked_sub(offset).unwrap_or(UNIX_EPOCH))
+    } else {
+        UNIX_EPOCH.checked_add(offset)
+    }
+}
+
+/// Return the number of days of `month` in `year`.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Return days since 1970-01-01 of the proleptic Gregorian calendar date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_all_formats() {
+        let date = UNIX_EPOCH + Duration::from_secs(784_111_777);
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"), Some(date));
+        assert_eq!(
+            parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(date)
+        );
+        assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Some(date));
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_164_800))
+        );
+        assert_eq!(
+            parse_http_date("Fri, 31 Dec 1969 23:59:59 GMT"),
+            UNIX_EPOCH.checked_sub(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn it_parses_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(784_111_777);
+        assert_eq!(parse_retry_after(" 5 ", now), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after("Thu, 01 Jan 1960 00:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after("99999999999999999999999", now),
+            Some(Duration::from_secs(u64::MAX))
+        );
+        assert_eq!(parse_retry_after("", now), None);
+        assert_eq!(parse_retry_after("-1", now), None);
+        assert_eq!(parse_retry_after("1.5", now), None);
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 25:49:37 GMT", now),
+            None
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Foo 1994 08:49:37 GMT", now),
+            None
+        );
+    }
+}
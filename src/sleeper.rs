@@ -0,0 +1,123 @@
+use std::{
+    future::{ready, Future, Ready},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A timer of an async runtime, used by [`retry_async_with`](crate::retry_async_with).
+pub trait Sleeper {
+    /// A future which completes after the duration.
+    type Sleep: Future<Output = ()>;
+
+    /// Return a future which completes after `duration`.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+/// A sleeper of Tokio runtime.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioSleeper;
+
+#[cfg(feature = "tokio")]
+impl Sleeper for TokioSleeper {
+    type Sleep = tokio::time::Sleep;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        tokio::time::sleep(duration)
+    }
+}
+
+/// A sleeper of async-std runtime.
+#[cfg(feature = "async-std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AsyncStdSleeper;
+
+#[cfg(feature = "async-std")]
+impl Sleeper for AsyncStdSleeper {
+    type Sleep = std::pin::Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        Box::pin(async_std::task::sleep(duration))
+    }
+}
+
+/// A sleeper of smol runtime.
+#[cfg(feature = "smol")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SmolSleeper;
+
+#[cfg(feature = "smol")]
+impl Sleeper for SmolSleeper {
+    type Sleep = SmolSleep;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        SmolSleep(smol::Timer::after(duration))
+    }
+}
+
+/// A future returned by [`SmolSleeper`].
+#[cfg(feature = "smol")]
+#[derive(Debug)]
+pub struct SmolSleep(smol::Timer);
+
+#[cfg(feature = "smol")]
+impl Future for SmolSleep {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        std::pin::Pin::new(&mut self.0).poll(cx).map(drop)
+    }
+}
+
+/// A sleeper which completes immediately, and records requested durations.
+///
+/// This is useful for testing without waiting. Clones share the records.
+#[derive(Debug, Default, Clone)]
+pub struct ManualSleeper {
+    slept: Arc<Mutex<Vec<Duration>>>,
+}
+
+impl ManualSleeper {
+    /// Create a new sleeper without records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return requested durations, in order.
+    pub fn slept(&self) -> Vec<Duration> {
+        self.slept.lock().unwrap().clone()
+    }
+}
+
+impl Sleeper for ManualSleeper {
+    type Sleep = Ready<()>;
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.slept.lock().unwrap().push(duration);
+        ready(())
+    }
+}
+
+#[cfg(all(test, any(feature = "async-std", feature = "smol")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "async-std")]
+    #[test]
+    fn async_std_sleeper() {
+        let start = std::time::Instant::now();
+        async_std::task::block_on(AsyncStdSleeper.sleep(Duration::from_millis(10)));
+        assert!(Duration::from_millis(10) <= start.elapsed());
+    }
+
+    #[cfg(feature = "smol")]
+    #[test]
+    fn smol_sleeper() {
+        let start = std::time::Instant::now();
+        smol::block_on(SmolSleeper.sleep(Duration::from_millis(10)));
+        assert!(Duration::from_millis(10) <= start.elapsed());
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+
+use crate::{Backoff, Sleeper};
+
+/// A stream which yields each duration of a strategy after the duration has elapsed.
+///
+/// This is handy for polling or reconnecting loops in `select!`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::{pin::Pin, time::Duration};
+/// use futures_core::Stream;
+/// use retry_durations::{ManualSleeper, Ticker};
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_secs(1))
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// let mut ticker = Ticker::new(ManualSleeper::new(), strategy);
+/// while let Some(x) = std::future::poll_fn(|cx| Pin::new(&mut ticker).poll_next(cx)).await {
+///     println!("{x:?}");
+/// }
+/// # }
+/// ```
+pub struct Ticker<S: Sleeper, I> {
+    sleeper: S,
+    durations: I,
+    sleep: Option<(Pin<Box<S::Sleep>>, Duration)>,
+}
+
+impl<S: Sleeper + fmt::Debug, I: fmt::Debug> fmt::Debug for Ticker<S, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ticker")
+            .field("sleeper", &self.sleeper)
+            .field("durations", &self.durations)
+            .field(
+                "sleeping",
+                &self.sleep.as_ref().map(|(_, duration)| duration),
+            )
+            .finish()
+    }
+}
+
+impl<S: Sleeper, I: Iterator<Item = Duration>> Ticker<S, I> {
+    /// Create a new stream of `strategy`, waiting each duration by `sleeper`.
+    pub fn new<T: IntoIterator<IntoIter = I>>(sleeper: S, strategy: T) -> Self {
+        Self {
+            sleeper,
+            durations: strategy.into_iter(),
+            sleep: None,
+        }
+    }
+
+    /// Unwrap the inner strategy.
+    pub fn into_inner(self) -> I {
+        self.durations
+    }
+}
+
+impl<S, I> Stream for Ticker<S, I>
+where
+    S: Sleeper + Unpin,
+    I: Iterator<Item = Duration> + Unpin,
+{
+    type Item = Duration;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let (sleep, duration) = match &mut this.sleep {
+            Some(sleep) => sleep,
+            None => {
+                let Some(duration) = this.durations.next() else {
+                    return Poll::Ready(None);
+                };
+                this.sleep
+                    .insert((Box::pin(this.sleeper.sleep(duration)), duration))
+            }
+        };
+        let duration = *duration;
+        match sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                this.sleep = None;
+                Poll::Ready(Some(duration))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.durations.size_hint();
+        let pending = usize::from(self.sleep.is_some());
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+/// A stream which runs a fallible future on a strategy, yielding the result of each attempt.
+///
+/// Each error is yielded as it happens, then `op` is run again after the next duration.
+/// The stream ends after a success, or after an error when the strategy has no more durations.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use std::{pin::Pin, time::Duration};
+/// use futures_core::Stream;
+/// use retry_durations::{ManualSleeper, Retrying};
+///
+/// let strategy = retry_durations::builder()
+///     .duration(Duration::from_secs(1))
+///     .max_retries(3)
+///     .build()
+///     .unwrap();
+/// let mut attempts = Retrying::new(ManualSleeper::new(), strategy, || async {
+///     Err::<(), _>("unavailable")
+/// });
+/// while let Some(x) = std::future::poll_fn(|cx| Pin::new(&mut attempts).poll_next(cx)).await {
+///     println!("{x:?}");
+/// }
+/// # }
+/// ```
+pub struct Retrying<S: Sleeper, B, F, Fut> {
+    sleeper: S,
+    strategy: B,
+    op: F,
+    attempt: u32,
+    state: State<S::Sleep, Fut>,
+}
+
+enum State<Sleep, Fut> {
+    Idle,
+    Running(Pin<Box<Fut>>),
+    Sleeping(Pin<Box<Sleep>>),
+    Done,
+}
+
+impl<S: Sleeper, B: Backoff, F, Fut> Retrying<S, B, F, Fut> {
+    /// Create a new stream running `op` on `strategy`, waiting each duration by `sleeper`.
+    pub fn new(sleeper: S, strategy: B, op: F) -> Self {
+        Self {
+            sleeper,
+            strategy,
+            op,
+            attempt: 0,
+            state: State::Idle,
+        }
+    }
+
+    /// Unwrap the inner strategy.
+    pub fn into_inner(self) -> B {
+        self.strategy
+    }
+}
+
+impl<S, B, F, Fut> fmt::Debug for Retrying<S, B, F, Fut>
+where
+    S: Sleeper + fmt::Debug,
+    B: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            State::Idle => "Idle",
+            State::Running(_) => "Running",
+            State::Sleeping(_) => "Sleeping",
+            State::Done => "Done",
+        };
+        f.debug_struct("Retrying")
+            .field("sleeper", &self.sleeper)
+            .field("strategy", &self.strategy)
+            .field("attempt", &self.attempt)
+            .field("state", &state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, S, B, F, Fut> Stream for Retrying<S, B, F, Fut>
+where
+    S: Sleeper + Unpin,
+    B: Backoff + Unpin,
+    F: FnMut() -> Fut + Unpin,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle => this.state = State::Running(Box::pin((this.op)())),
+                State::Running(future) => {
+                    let result = match future.as_mut().poll(cx) {
+                        Poll::Ready(result) => result,
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    this.state = match &result {
+                        Ok(_) => State::Done,
+                        Err(_) => match this.strategy.next_delay(this.attempt) {
+                            Some(duration) => {
+                                this.attempt = this.attempt.saturating_add(1);
+                                State::Sleeping(Box::pin(this.sleeper.sleep(duration)))
+                            }
+                            None => State::Done,
+                        },
+                    };
+                    return Poll::Ready(Some(result));
+                }
+                State::Sleeping(sleep) => match sleep.as_mut().poll(cx) {
+                    Poll::Ready(()) => this.state = State::Idle,
+                    Poll::Pending => return Poll::Pending,
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+
+    use super::*;
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn it_ticks_after_durations() {
+        let strategy = crate::builder()
+            .duration(Duration::from_secs(1))
+            .jitter(0.0)
+            .max_retries(3)
+            .build()
+            .unwrap();
+        let start = tokio::time::Instant::now();
+        let mut ticker = Ticker::new(crate::TokioSleeper, strategy);
+        let mut xs = Vec::new();
+        while let Some(x) = poll_fn(|cx| Pin::new(&mut ticker).poll_next(cx)).await {
+            assert_eq!(start.elapsed(), xs.iter().sum::<Duration>() + x);
+            xs.push(x);
+        }
+        assert_eq!(xs.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn it_sleeps_by_sleeper() {
+        let sleeper = crate::ManualSleeper::new();
+        let strategy = crate::builder().max_retries(3).build().unwrap();
+        let mut ticker = Ticker::new(sleeper.clone(), strategy);
+        assert_eq!(ticker.size_hint(), (3, Some(3)));
+        let mut xs = Vec::new();
+        while let Some(x) = poll_fn(|cx| Pin::new(&mut ticker).poll_next(cx)).await {
+            xs.push(x);
+        }
+        assert_eq!(sleeper.slept(), xs);
+    }
+
+    #[tokio::test]
+    async fn it_yields_each_attempt() {
+        let sleeper = crate::ManualSleeper::new();
+        let strategy = crate::builder()
+            .duration(Duration::from_secs(1))
+            .jitter(0.0)
+            .max_retries(3)
+            .build()
+            .unwrap();
+        let mut count = 0;
+        let mut attempts = Retrying::new(sleeper.clone(), strategy, || {
+            count += 1;
+            let attempt = count;
+            async move {
+                if attempt < 3 {
+                    Err(attempt)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        });
+        let mut xs = Vec::new();
+        while let Some(x) = poll_fn(|cx| Pin::new(&mut attempts).poll_next(cx)).await {
+            xs.push(x);
+        }
+        assert_eq!(xs, [Err(1), Err(2), Ok(3)]);
+        assert_eq!(
+            sleeper.slept(),
+            [Duration::from_secs(1), Duration::from_secs(2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_ends_with_strategy() {
+        let strategy = crate::builder().max_retries(1).build().unwrap();
+        let mut attempts = Retrying::new(crate::ManualSleeper::new(), strategy, || async {
+            Err::<(), _>("failed")
+        });
+        let mut xs = Vec::new();
+        while let Some(x) = poll_fn(|cx| Pin::new(&mut attempts).poll_next(cx)).await {
+            xs.push(x);
+        }
+        assert_eq!(xs, [Err("failed"), Err("failed")]);
+        assert!(format!("{attempts:?}").contains("Done"));
+    }
+}